@@ -1,6 +1,7 @@
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
-use std::ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign};
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Sub, SubAssign};
 use std::str::FromStr;
 
 pub trait PrintBoard {
@@ -125,8 +126,206 @@ impl<T: PointOption + Into<isize>> Point<T> {
     pub fn distance(&self, other: &Point<T>) -> usize {
         self.x.into().abs_diff(other.x.into()) + self.y.into().abs_diff(other.y.into())
     }
+
+    /// The integer square root of `x^2 + y^2`.
+    pub fn euclidean_norm(&self) -> usize {
+        let x: isize = self.x.into();
+        let y: isize = self.y.into();
+        isqrt((x * x + y * y) as usize)
+    }
+}
+
+impl<T: PointOption + Mul<Output = T>> Point<T> {
+    pub fn dot(&self, other: &Point<T>) -> T {
+        self.x * other.x + self.y * other.y
+    }
+
+    /// Applies a 2x2 integer matrix `[a,b,c,d]` as `(a*x+b*y, c*x+d*y)`, so rotations and
+    /// reflections can be expressed as matrices rather than match arms.
+    pub fn transform(&self, matrix: &[T; 4]) -> Point<T> {
+        Point {
+            x: self.x * matrix[0] + self.y * matrix[1],
+            y: self.x * matrix[2] + self.y * matrix[3],
+        }
+    }
+
+    /// Whether `a`, `b`, and `c` lie on a common straight line, via the cross product
+    /// `(b - a) x (c - a)` so the test stays exact on integers.
+    pub fn collinear(a: Point<T>, b: Point<T>, c: Point<T>) -> bool {
+        (b.x - a.x) * (c.y - a.y) == (b.y - a.y) * (c.x - a.x)
+    }
+}
+
+impl<T: PointOption + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Point {
+            x: self.x * rhs,
+            y: self.y * rhs,
+        }
+    }
+}
+
+/// Computes `floor(sqrt(n))` using Newton's method, avoiding floating point error.
+fn isqrt(n: usize) -> usize {
+    if n == 0 {
+        return 0;
+    }
+    let mut x = n;
+    let mut y = x.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
 }
 
+impl Point {
+    /// Each component's sign: `-1`, `0`, or `1`. Useful for stepping a rope/tail toward a head.
+    pub fn signum(&self) -> Point {
+        Point {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+
+    pub fn abs(&self) -> Point {
+        Point {
+            x: self.x.abs(),
+            y: self.y.abs(),
+        }
+    }
+
+    /// The Chebyshev distance: `max(|dx|, |dy|)`.
+    pub fn max_norm(&self) -> isize {
+        self.x.abs().max(self.y.abs())
+    }
+
+    /// Converts a non-negative point into a `Point<usize>` suitable for indexing a
+    /// `Vec<Vec<T>>`, failing if either component is negative.
+    pub fn to_index(&self) -> Option<Point<usize>> {
+        Some(Point {
+            x: usize::try_from(self.x).ok()?,
+            y: usize::try_from(self.y).ok()?,
+        })
+    }
+
+    /// Moves one cell in `dir`, respecting the inclusive `upper_left..=lower_right` box per
+    /// `mode`: `Clamp` saturates at the last in-bounds coordinate, `Wrap` rolls around to the
+    /// opposite edge.
+    pub fn step(&self, dir: Direction, bounds: (Point, Point), mode: Boundary) -> Point {
+        let (upper_left, lower_right) = bounds;
+        let moved = *self + dir.as_vector();
+        match mode {
+            Boundary::Clamp => Point {
+                x: moved.x.clamp(upper_left.x, lower_right.x),
+                y: moved.y.clamp(upper_left.y, lower_right.y),
+            },
+            Boundary::Wrap => Point {
+                x: wrap(moved.x, upper_left.x, lower_right.x),
+                y: wrap(moved.y, upper_left.y, lower_right.y),
+            },
+        }
+    }
+}
+
+/// How `Point::step` should handle a move that would leave the grid's bounds.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Boundary {
+    Clamp,
+    Wrap,
+}
+
+/// Wraps `value` into the inclusive `min..=max` range, rolling around modulo `max - min + 1`.
+fn wrap(value: isize, min: isize, max: isize) -> isize {
+    let span = max - min + 1;
+    ((value - min).rem_euclid(span)) + min
+}
+
+fn gcd(a: isize, b: isize) -> isize {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The largest number of points (out of `points`) that lie on a common straight line.
+///
+/// For each anchor point, the others are grouped by their reduced direction from the anchor
+/// (the `(dx, dy)` delta divided by its `gcd`, with the sign canonicalized so opposite and
+/// scaled directions map to the same bucket); the largest bucket, plus the anchor itself and
+/// any exact duplicates, is a candidate for the answer.
+pub fn max_points_on_a_line(points: &[Point]) -> usize {
+    if points.len() <= 2 {
+        return points.len();
+    }
+
+    let mut best = 1;
+    for (i, &anchor) in points.iter().enumerate() {
+        let mut slopes: HashMap<(isize, isize), usize> = HashMap::new();
+        let mut duplicates = 0;
+        for (j, &other) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let dx = other.x - anchor.x;
+            let dy = other.y - anchor.y;
+            if dx == 0 && dy == 0 {
+                duplicates += 1;
+                continue;
+            }
+            let g = gcd(dx, dy);
+            let (mut ndx, mut ndy) = (dx / g, dy / g);
+            if ndx < 0 || (ndx == 0 && ndy < 0) {
+                ndx = -ndx;
+                ndy = -ndy;
+            }
+            *slopes.entry((ndx, ndy)).or_insert(0) += 1;
+        }
+        let max_on_line = slopes.values().copied().max().unwrap_or(0);
+        best = best.max(max_on_line + duplicates + 1);
+    }
+    best
+}
+
+/// Generates `orthogonal_neighbors`/`neighbors` for a concrete signed `Point<T>`. Negative
+/// offset literals require a signed element type, so this can't be written once generically
+/// over `PointOption` (which also covers the unsigned types) — see the request notes on
+/// `Point::neighbors`/`Point3d::neighbors`.
+macro_rules! impl_point_neighbors {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl Point<$t> {
+                /// The four orthogonally adjacent cells: Left, Right, Up, Down (matching
+                /// `Direction::as_vector`'s offsets).
+                pub fn orthogonal_neighbors(&self) -> [Point<$t>; 4] {
+                    let offsets: [($t, $t); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+                    offsets.map(|offset| *self + offset)
+                }
+
+                /// The Moore neighborhood: all 8 cells surrounding this point.
+                pub fn neighbors(&self) -> [Point<$t>; 8] {
+                    let offsets: [($t, $t); 8] = [
+                        (-1, -1),
+                        (0, -1),
+                        (1, -1),
+                        (-1, 0),
+                        (1, 0),
+                        (-1, 1),
+                        (0, 1),
+                        (1, 1),
+                    ];
+                    offsets.map(|offset| *self + offset)
+                }
+            }
+        )+
+    };
+}
+
+impl_point_neighbors!(i8, i16, i32, i64, isize);
+
 impl<T> Index<Point<usize>> for Vec<Vec<T>> {
     type Output = T;
 
@@ -243,6 +442,24 @@ impl Point3d {
             (self.x, self.y, self.z + 1).into(),
         ]
     }
+
+    /// All 26 cells surrounding this point, i.e. every `dx,dy,dz` in `{-1,0,1}` except `(0,0,0)`.
+    pub fn neighbors(&self) -> [Point3d; 26] {
+        let mut result = [Point3d::default(); 26];
+        let mut i = 0;
+        for dx in [-1, 0, 1] {
+            for dy in [-1, 0, 1] {
+                for dz in [-1, 0, 1] {
+                    if dx == 0 && dy == 0 && dz == 0 {
+                        continue;
+                    }
+                    result[i] = *self + (dx, dy, dz);
+                    i += 1;
+                }
+            }
+        }
+        result
+    }
 }
 
 impl<T: PointOption + FromStr> FromStr for Point3d<T> {
@@ -287,3 +504,339 @@ impl<T: PointOption> From<(T, T, T)> for Point3d<T> {
         }
     }
 }
+
+fn bounds3d(cubes: &HashSet<Point3d>) -> Option<(Point3d, Point3d)> {
+    let mut cubes = cubes.iter().copied();
+    let first = cubes.next()?;
+    let (mut min, mut max) = (first, first);
+    for cube in cubes {
+        min.x = min.x.min(cube.x);
+        min.y = min.y.min(cube.y);
+        min.z = min.z.min(cube.z);
+        max.x = max.x.max(cube.x);
+        max.y = max.y.max(cube.y);
+        max.z = max.z.max(cube.z);
+    }
+    Some((min, max))
+}
+
+/// The total surface area of a set of voxels: for every cube, how many of its six
+/// `Point3d::borders()` are not themselves in `cubes`.
+pub fn surface_area(cubes: &HashSet<Point3d>) -> usize {
+    cubes
+        .iter()
+        .map(|cube| {
+            cube.borders()
+                .iter()
+                .filter(|border| !cubes.contains(border))
+                .count()
+        })
+        .sum()
+}
+
+/// The surface area of a set of voxels reachable from the outside, excluding faces sealed
+/// inside interior air pockets. Flood-fills the empty space around `cubes`' bounding box
+/// (padded by one cell in each direction) over `Point3d::borders()`, then counts only the
+/// faces that border reachable exterior air.
+pub fn exterior_surface_area(cubes: &HashSet<Point3d>) -> usize {
+    let Some((min, max)) = bounds3d(cubes) else {
+        return 0;
+    };
+    let padded_min = min + (-1, -1, -1);
+    let padded_max = max + (1, 1, 1);
+    let in_padded_bounds = |p: &Point3d| {
+        p.x >= padded_min.x
+            && p.x <= padded_max.x
+            && p.y >= padded_min.y
+            && p.y <= padded_max.y
+            && p.z >= padded_min.z
+            && p.z <= padded_max.z
+    };
+
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+    visited.insert(padded_min);
+    queue.push_back(padded_min);
+
+    let mut area = 0;
+    while let Some(current) = queue.pop_front() {
+        for border in current.borders() {
+            if !in_padded_bounds(&border) || visited.contains(&border) {
+                continue;
+            }
+            if cubes.contains(&border) {
+                area += 1;
+                continue;
+            }
+            visited.insert(border);
+            queue.push_back(border);
+        }
+    }
+    area
+}
+
+/// A sparse grid keyed by `P` (a `Point` by default), for puzzles where a `Vec<Vec<T>>`
+/// would waste memory or can't represent negative coordinates.
+#[derive(Debug, Clone)]
+pub struct Grid<T, P = Point> {
+    cells: HashMap<P, T>,
+}
+
+impl<T, P> Default for Grid<T, P> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+}
+
+impl<T, P> Grid<T, P> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<T, P: Eq + Hash> Grid<T, P> {
+    /// Inserts `value` at `point`, returning the previous value if one was present.
+    pub fn insert<I: Into<P>>(&mut self, point: I, value: T) -> Option<T> {
+        self.cells.insert(point.into(), value)
+    }
+}
+
+impl<T: Default + Clone, P: Eq + Hash> Grid<T, P> {
+    /// Returns the value at `point`, or `T::default()` if nothing has been inserted there.
+    pub fn get<I: Into<P>>(&self, point: I) -> T {
+        self.cells.get(&point.into()).cloned().unwrap_or_default()
+    }
+}
+
+impl<T> Grid<T, Point> {
+    /// The minimum and maximum inserted points, i.e. the occupied bounding box.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        let mut keys = self.cells.keys().copied();
+        let first = keys.next()?;
+        let (mut min, mut max) = (first, first);
+        for key in keys {
+            min.x = min.x.min(key.x);
+            min.y = min.y.min(key.y);
+            max.x = max.x.max(key.x);
+            max.y = max.y.max(key.y);
+        }
+        Some((min, max))
+    }
+}
+
+impl<T: Display + Default + Clone> PrintBoard for Grid<T, Point> {
+    fn print_board(&self) -> String {
+        match self.bounds() {
+            None => String::new(),
+            Some((min, max)) => (min.y..=max.y)
+                .map(|y| {
+                    (min.x..=max.x)
+                        .map(|x| self.get((x, y)).to_string())
+                        .collect::<String>()
+                })
+                .reduce(|left, right| format!("{}\n{}", left, right))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// A generic N-dimensional vector, for puzzles that outgrow `Point`/`Point3d`'s two and
+/// three axes (e.g. 4D hypercubes, higher-dimensional Conway cubes).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct VecN<const N: usize, T: PointOption>([T; N]);
+
+impl<const N: usize, T: PointOption> Default for VecN<N, T> {
+    fn default() -> Self {
+        Self([T::default(); N])
+    }
+}
+
+impl<const N: usize, T: PointOption> From<[T; N]> for VecN<N, T> {
+    fn from(values: [T; N]) -> Self {
+        Self(values)
+    }
+}
+
+impl<const N: usize, T: PointOption> Index<usize> for VecN<N, T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<const N: usize, T: PointOption> IndexMut<usize> for VecN<N, T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<const N: usize, T: PointOption> Add for VecN<N, T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let mut result = self.0;
+        for (l, r) in result.iter_mut().zip(rhs.0) {
+            *l = *l + r;
+        }
+        Self(result)
+    }
+}
+
+impl<const N: usize, T: PointOption> AddAssign for VecN<N, T> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0) {
+            *l = *l + r;
+        }
+    }
+}
+
+impl<const N: usize, T: PointOption> Sub for VecN<N, T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let mut result = self.0;
+        for (l, r) in result.iter_mut().zip(rhs.0) {
+            *l = *l - r;
+        }
+        Self(result)
+    }
+}
+
+impl<const N: usize, T: PointOption> SubAssign for VecN<N, T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (l, r) in self.0.iter_mut().zip(rhs.0) {
+            *l = *l - r;
+        }
+    }
+}
+
+impl<const N: usize, T: PointOption + Into<isize>> VecN<N, T> {
+    /// The Manhattan distance between the two vectors: the sum of the per-axis `abs_diff`.
+    pub fn distance(&self, other: &Self) -> usize {
+        (0..N)
+            .map(|i| self.0[i].into().abs_diff(other.0[i].into()))
+            .sum()
+    }
+}
+
+/// Generates `neighbors` for a concrete signed `VecN<N, T>`. Negative offset literals require
+/// a signed element type, so (as with `Point::neighbors`) this can't be written once
+/// generically over `PointOption`, which also covers the unsigned types.
+macro_rules! impl_vecn_neighbors {
+    ($($t:ty),+ $(,)?) => {
+        $(
+            impl<const N: usize> VecN<N, $t> {
+                /// Every vector adjacent to this one, i.e. the cartesian product of `{-1,0,1}`
+                /// on each axis minus the all-zero offset: `3^N - 1` neighbors in total.
+                pub fn neighbors(&self) -> Vec<VecN<N, $t>> {
+                    let mut offsets = vec![[0 as $t; N]];
+                    for axis in 0..N {
+                        let mut next = Vec::with_capacity(offsets.len() * 3);
+                        for offset in &offsets {
+                            for delta in [-1 as $t, 0 as $t, 1 as $t] {
+                                let mut extended = *offset;
+                                extended[axis] = delta;
+                                next.push(extended);
+                            }
+                        }
+                        offsets = next;
+                    }
+
+                    offsets
+                        .into_iter()
+                        .filter(|offset| offset.iter().any(|&delta| delta != 0 as $t))
+                        .map(|offset| {
+                            let mut result = self.0;
+                            for (component, delta) in result.iter_mut().zip(offset) {
+                                *component += delta;
+                            }
+                            VecN(result)
+                        })
+                        .collect()
+                }
+            }
+        )+
+    };
+}
+
+impl_vecn_neighbors!(i8, i16, i32, i64, isize);
+
+impl<const N: usize, T: PointOption> VecN<N, T> {
+    /// Applies `f` to every component, failing the whole conversion if any component does,
+    /// e.g. converting an `isize` vector to `usize` when a component is negative.
+    pub fn try_map<U: PointOption, E>(
+        &self,
+        f: impl Fn(T) -> Result<U, E>,
+    ) -> Result<VecN<N, U>, E> {
+        let mut result = [U::default(); N];
+        for (slot, &value) in result.iter_mut().zip(self.0.iter()) {
+            *slot = f(value)?;
+        }
+        Ok(VecN(result))
+    }
+}
+
+impl<T: PointOption> From<Point<T>> for VecN<2, T> {
+    fn from(p: Point<T>) -> Self {
+        Self([p.x, p.y])
+    }
+}
+
+impl<T: PointOption> From<VecN<2, T>> for Point<T> {
+    fn from(v: VecN<2, T>) -> Self {
+        Self { x: v.0[0], y: v.0[1] }
+    }
+}
+
+impl<T: PointOption> From<Point3d<T>> for VecN<3, T> {
+    fn from(p: Point3d<T>) -> Self {
+        Self([p.x, p.y, p.z])
+    }
+}
+
+impl<T: PointOption> From<VecN<3, T>> for Point3d<T> {
+    fn from(v: VecN<3, T>) -> Self {
+        Self {
+            x: v.0[0],
+            y: v.0[1],
+            z: v.0[2],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collinear_detects_a_line() {
+        let a: Point = (0, 0).into();
+        let b: Point = (1, 1).into();
+        let c: Point = (2, 2).into();
+        assert!(Point::collinear(a, b, c));
+    }
+
+    #[test]
+    fn collinear_rejects_a_bend() {
+        let a: Point = (0, 0).into();
+        let b: Point = (1, 1).into();
+        let c: Point = (2, 3).into();
+        assert!(!Point::collinear(a, b, c));
+    }
+
+    #[test]
+    fn max_points_on_a_line_finds_the_largest_collinear_group() {
+        let points: Vec<Point> = vec![
+            (0, 0).into(),
+            (1, 1).into(),
+            (2, 2).into(),
+            (3, 3).into(),
+            (1, 0).into(),
+            (2, 1).into(),
+        ];
+        assert_eq!(max_points_on_a_line(&points), 4);
+    }
+}